@@ -0,0 +1,126 @@
+/// Magic-number signatures we recognize, checked in order against the leading bytes of a
+/// resource's content. Falls back to extension-based guessing when nothing matches, since some
+/// content (plain text, source maps, ...) has no reliable signature.
+const SIGNATURES: &[(&[u8], &str)] = &[
+  (b"\x89PNG\r\n\x1a\n", "image/png"),
+  (b"\xff\xd8\xff", "image/jpeg"),
+  (b"GIF87a", "image/gif"),
+  (b"GIF89a", "image/gif"),
+  (b"%PDF-", "application/pdf"),
+  (b"\x00\x61\x73\x6d", "application/wasm"),
+  (b"PK\x03\x04", "application/zip"),
+  (b"\x1a\x45\xdf\xa3", "video/webm"),
+  (b"ftypmp4", "video/mp4"),
+  (b"ID3", "audio/mpeg"),
+  (b"OggS", "audio/ogg"),
+];
+
+pub struct MimeType;
+
+impl MimeType {
+  /// Sniffs `content`'s leading bytes for a known signature, falling back to guessing from
+  /// `uri`'s extension when nothing matches.
+  pub fn parse(content: &[u8], uri: &str) -> String {
+    Self::sniff(content)
+      .or_else(|| Self::from_extension(uri))
+      .unwrap_or_else(|| "text/plain".into())
+      .to_string()
+  }
+
+  fn sniff(content: &[u8]) -> Option<&'static str> {
+    // `ftypmp4`'s signature starts 4 bytes in (after the box size), everything else is anchored
+    // at the start of the file.
+    if content.len() >= 12 && &content[4..11] == b"ftypmp4" {
+      return Some("video/mp4");
+    }
+    // RIFF is a generic container; WebP, WAVE, and AVI all start with `RIFF` + a 4-byte size,
+    // and only differ in the form type at offset 8, so that has to be checked before committing
+    // to any one of them.
+    if content.starts_with(b"RIFF") && content.len() >= 12 {
+      return match &content[8..12] {
+        b"WEBP" => Some("image/webp"),
+        b"WAVE" => Some("audio/wav"),
+        b"AVI " => Some("video/x-msvideo"),
+        _ => None,
+      };
+    }
+    SIGNATURES
+      .iter()
+      .find(|(magic, _)| content.starts_with(magic))
+      .map(|(_, mime)| *mime)
+  }
+
+  fn from_extension(uri: &str) -> Option<&'static str> {
+    let extension = uri.rsplit('.').next()?.to_lowercase();
+    Some(match extension.as_str() {
+      "html" | "htm" => "text/html",
+      "css" => "text/css",
+      "js" | "mjs" => "text/javascript",
+      "json" => "application/json",
+      "svg" => "image/svg+xml",
+      "png" => "image/png",
+      "jpg" | "jpeg" => "image/jpeg",
+      "gif" => "image/gif",
+      "webp" => "image/webp",
+      "ico" => "image/x-icon",
+      "wasm" => "application/wasm",
+      "mp4" => "video/mp4",
+      "webm" => "video/webm",
+      "mp3" => "audio/mpeg",
+      "wav" => "audio/wav",
+      "ogg" => "audio/ogg",
+      "txt" => "text/plain",
+      _ => return None,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn riff(form_type: &[u8; 4]) -> Vec<u8> {
+    let mut content = b"RIFF".to_vec();
+    content.extend_from_slice(&[0, 0, 0, 0]); // chunk size, irrelevant to sniffing
+    content.extend_from_slice(form_type);
+    content
+  }
+
+  #[test]
+  fn sniffs_webp() {
+    assert_eq!(MimeType::parse(&riff(b"WEBP"), "file"), "image/webp");
+  }
+
+  #[test]
+  fn sniffs_wav_not_webp() {
+    assert_eq!(MimeType::parse(&riff(b"WAVE"), "file.wav"), "audio/wav");
+  }
+
+  #[test]
+  fn sniffs_avi_not_webp() {
+    assert_eq!(MimeType::parse(&riff(b"AVI "), "file"), "video/x-msvideo");
+  }
+
+  #[test]
+  fn falls_back_to_extension_for_unrecognized_riff_form() {
+    assert_eq!(MimeType::parse(&riff(b"????"), "file.txt"), "text/plain");
+  }
+
+  #[test]
+  fn sniffs_png() {
+    assert_eq!(
+      MimeType::parse(b"\x89PNG\r\n\x1a\nrest", "file"),
+      "image/png"
+    );
+  }
+
+  #[test]
+  fn falls_back_to_extension_when_unsniffable() {
+    assert_eq!(MimeType::parse(b"plain text", "file.css"), "text/css");
+  }
+
+  #[test]
+  fn falls_back_to_text_plain_when_nothing_matches() {
+    assert_eq!(MimeType::parse(b"???", "file"), "text/plain");
+  }
+}
@@ -0,0 +1,72 @@
+use std::{
+  path::{Path, PathBuf},
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(target_os = "windows")]
+use crate::webview::win::web_context::EnvironmentHandle;
+
+static EPHEMERAL_SEQ: AtomicUsize = AtomicUsize::new(0);
+
+/// Controls the data (cookies, cache, local storage, ...) a [`WebView`](crate::WebView) is
+/// backed by.
+///
+/// Pass the same `WebContext` to every window that should share its session, or build a fresh
+/// one per window to keep them isolated. An ephemeral context -- one created without a
+/// `data_directory` -- behaves like a private session: its backing directory is a temporary one
+/// that's removed once every webview using it is dropped.
+///
+/// The underlying engine environment is reference counted and only built the first time it's
+/// needed; it's released once the last [`InnerWebView`](crate::webview::InnerWebView) built from
+/// it is dropped.
+#[derive(Debug)]
+pub struct WebContext {
+  data_directory: PathBuf,
+  ephemeral: bool,
+}
+
+impl WebContext {
+  /// Creates a new `WebContext`.
+  ///
+  /// `data_directory` is where the webview engine persists cookies, cache, and local storage.
+  /// Pass `None` to get an ephemeral, private-browsing-like context backed by a temporary
+  /// directory instead.
+  pub fn new(data_directory: Option<PathBuf>) -> Self {
+    match data_directory {
+      Some(data_directory) => Self {
+        data_directory,
+        ephemeral: false,
+      },
+      None => Self {
+        data_directory: std::env::temp_dir().join(format!(
+          "wry-webcontext-{}-{}",
+          std::process::id(),
+          EPHEMERAL_SEQ.fetch_add(1, Ordering::Relaxed)
+        )),
+        ephemeral: true,
+      },
+    }
+  }
+
+  /// The directory backing this context's session data.
+  pub fn data_directory(&self) -> &Path {
+    &self.data_directory
+  }
+
+  /// Whether this context was created without an explicit data directory and will have its
+  /// backing directory removed once every webview built from it is dropped.
+  pub fn is_ephemeral(&self) -> bool {
+    self.ephemeral
+  }
+
+  #[cfg(target_os = "windows")]
+  pub(crate) fn environment_handle(&self) -> EnvironmentHandle {
+    EnvironmentHandle::new(self.data_directory.clone(), self.ephemeral)
+  }
+}
+
+impl Default for WebContext {
+  fn default() -> Self {
+    Self::new(None)
+  }
+}
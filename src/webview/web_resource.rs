@@ -0,0 +1,165 @@
+use std::{
+  collections::HashMap,
+  io::{Read, Seek},
+};
+
+/// The request handed to a custom-protocol resolver: the (already protocol-workaround-undone)
+/// URI plus whatever headers the engine forwarded, lower-cased by name for easy lookup (e.g.
+/// `Range`).
+pub struct Request {
+  pub uri: String,
+  pub headers: HashMap<String, String>,
+
+  /// Set when the builder's `csp` is configured: the nonces wry will substitute into that CSP's
+  /// `%SCRIPT_NONCE%`/`%STYLE_NONCE%` placeholders for this request, so the resolver can tag its
+  /// own `<script>`/`<style>` elements to match before the policy header reaches the page.
+  pub csp_nonce: Option<CspNonce>,
+}
+
+impl Request {
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self.headers.get(&name.to_lowercase()).map(String::as_str)
+  }
+}
+
+/// The pair of per-load nonces generated for one request when a builder-level CSP is configured.
+#[derive(Debug, Clone)]
+pub struct CspNonce {
+  pub script: String,
+  pub style: String,
+}
+
+/// What a custom-protocol resolver returns: a status code, headers, and a body. The body is
+/// `Read + Seek` rather than a buffer so large media can be streamed -- and so a `Range` request
+/// can `seek` straight to the requested offset -- instead of being loaded into memory up front.
+pub struct Response {
+  pub status: u16,
+  pub headers: HashMap<String, String>,
+  pub body: Box<dyn Read + Seek>,
+}
+
+impl Response {
+  /// A `200 OK` response with no headers set. Add a `Content-Type` via
+  /// [`Response::with_mimetype`], or leave it unset to have one sniffed from the body.
+  pub fn new(body: Box<dyn Read + Seek>) -> Self {
+    Self {
+      status: 200,
+      headers: HashMap::new(),
+      body,
+    }
+  }
+
+  pub fn with_mimetype(mut self, mimetype: &str) -> Self {
+    self
+      .headers
+      .insert("Content-Type".into(), mimetype.to_string());
+    self
+  }
+}
+
+/// Why a `Range` header couldn't be turned into a [`ByteRange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeError {
+  /// Not a `bytes=start-end` spec at all; callers should treat this the same as no `Range`
+  /// header being present, per RFC 7233 ("a server ... MUST ignore [an invalid Range header]").
+  Malformed,
+  /// Syntactically valid but outside the body -- callers should respond `416 Range Not
+  /// Satisfiable` rather than failing the request.
+  Unsatisfiable,
+}
+
+/// An inclusive byte range parsed out of a `Range: bytes=start-end` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+  pub start: u64,
+  pub end: u64,
+}
+
+impl ByteRange {
+  /// Parses the value of a `Range` header, e.g. `bytes=0-1023` or `bytes=1024-`, against a body
+  /// of `len` bytes. An open-ended range (`bytes=1024-`) resolves to the end of the body; a
+  /// `last-byte-pos` past the end is clamped to it (RFC 7233 section 2.1), but a `first-byte-pos`
+  /// at or past `len` is `Unsatisfiable` rather than silently clamped.
+  pub fn parse(header: &str, len: u64) -> Result<Self, RangeError> {
+    let spec = header.strip_prefix("bytes=").ok_or(RangeError::Malformed)?;
+    let (start, end) = spec.split_once('-').ok_or(RangeError::Malformed)?;
+    let start: u64 = start.parse().map_err(|_| RangeError::Malformed)?;
+    if start >= len {
+      return Err(RangeError::Unsatisfiable);
+    }
+    let end = if end.is_empty() {
+      len - 1
+    } else {
+      end
+        .parse::<u64>()
+        .map_err(|_| RangeError::Malformed)?
+        .min(len - 1)
+    };
+    if start > end {
+      return Err(RangeError::Malformed);
+    }
+    Ok(Self { start, end })
+  }
+
+  pub fn byte_count(&self) -> u64 {
+    self.end - self.start + 1
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_closed_range() {
+    assert_eq!(
+      ByteRange::parse("bytes=0-99", 1000),
+      Ok(ByteRange { start: 0, end: 99 })
+    );
+  }
+
+  #[test]
+  fn parses_open_ended_range() {
+    assert_eq!(
+      ByteRange::parse("bytes=900-", 1000),
+      Ok(ByteRange { start: 900, end: 999 })
+    );
+  }
+
+  #[test]
+  fn clamps_end_past_body_length() {
+    assert_eq!(
+      ByteRange::parse("bytes=0-9999", 1000),
+      Ok(ByteRange { start: 0, end: 999 })
+    );
+  }
+
+  #[test]
+  fn rejects_start_past_body_length_as_unsatisfiable() {
+    assert_eq!(
+      ByteRange::parse("bytes=99999-100000", 100),
+      Err(RangeError::Unsatisfiable)
+    );
+  }
+
+  #[test]
+  fn rejects_start_equal_to_length_as_unsatisfiable() {
+    assert_eq!(
+      ByteRange::parse("bytes=100-", 100),
+      Err(RangeError::Unsatisfiable)
+    );
+  }
+
+  #[test]
+  fn rejects_missing_prefix_as_malformed() {
+    assert_eq!(ByteRange::parse("0-99", 1000), Err(RangeError::Malformed));
+  }
+
+  #[test]
+  fn rejects_inverted_range_as_malformed() {
+    assert_eq!(
+      ByteRange::parse("bytes=99-0", 1000),
+      Err(RangeError::Malformed)
+    );
+  }
+}
@@ -0,0 +1,246 @@
+// WebView2 doesn't expose file-drag/drop itself, so `FileDropController` implements the Win32
+// `IDropTarget` OLE interface directly on the webview's `HWND` and translates its
+// `DragEnter`/`DragOver`/`DragLeave`/`Drop` callbacks into `FileDropEvent`s.
+use std::{
+  cell::Cell,
+  ffi::OsString,
+  os::windows::ffi::OsStringExt,
+  path::PathBuf,
+  ptr,
+  rc::Rc,
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+use winapi::{
+  ctypes::c_void,
+  shared::{
+    guiddef::REFIID,
+    minwindef::{DWORD, ULONG},
+    windef::{HWND, POINTL},
+    winerror::{E_NOINTERFACE, HRESULT, S_OK},
+  },
+  um::{
+    objidl::IDataObject,
+    oleidl::{IDropTarget, IDropTargetVtbl, DROPEFFECT, DROPEFFECT_COPY, DROPEFFECT_NONE, IID_IDropTarget},
+    ole2::{RegisterDragDrop, ReleaseStgMedium, RevokeDragDrop},
+    shellapi::DragQueryFileW,
+    unknwnbase::{IUnknown, IUnknownVtbl, IID_IUnknown},
+    winuser::{GetDpiForWindow, ScreenToClient},
+  },
+};
+
+use crate::FileDropEvent;
+
+/// Holds the `IDropTarget` registered for a webview's `HWND` for the webview's lifetime; revokes
+/// it, and releases our reference to it, on drop.
+pub struct FileDropController {
+  hwnd: Cell<HWND>,
+  drop_target: Cell<*mut FileDropTarget>,
+}
+
+impl FileDropController {
+  pub fn new() -> Self {
+    Self {
+      hwnd: Cell::new(ptr::null_mut()),
+      drop_target: Cell::new(ptr::null_mut()),
+    }
+  }
+
+  pub fn listen(&mut self, hwnd: HWND, handler: crate::FileDropHandler) {
+    // COM objects manage their own lifetime via `IUnknown::Release`, so we hand ownership to a
+    // raw pointer here rather than a `Box` -- `release` frees it once `RevokeDragDrop`'s matching
+    // `Release` call (and any others) bring the refcount to zero.
+    let drop_target = Box::into_raw(Box::new(FileDropTarget::new(hwnd, handler)));
+    unsafe {
+      RegisterDragDrop(hwnd, drop_target as *mut IDropTarget);
+    }
+    self.hwnd.set(hwnd);
+    self.drop_target.set(drop_target);
+  }
+}
+
+impl Drop for FileDropController {
+  fn drop(&mut self) {
+    let drop_target = self.drop_target.get();
+    if !drop_target.is_null() {
+      unsafe {
+        RevokeDragDrop(self.hwnd.get());
+        release(drop_target as *mut IUnknown);
+      }
+    }
+  }
+}
+
+#[repr(C)]
+struct FileDropTarget {
+  vtbl: *const IDropTargetVtbl,
+  ref_count: AtomicUsize,
+  hwnd: HWND,
+  handler: Rc<crate::FileDropHandler>,
+  // The paths read on `DragEnter`, re-sent with every `DragOver`'s `Hovered` event since
+  // `DragOver` isn't handed an `IDataObject` to read them from.
+  hovered_paths: std::cell::RefCell<Vec<PathBuf>>,
+}
+
+static VTBL: IDropTargetVtbl = IDropTargetVtbl {
+  parent: IUnknownVtbl {
+    QueryInterface: query_interface,
+    AddRef: add_ref,
+    Release: release,
+  },
+  DragEnter: drag_enter,
+  DragOver: drag_over,
+  DragLeave: drag_leave,
+  Drop: drop_,
+};
+
+impl FileDropTarget {
+  fn new(hwnd: HWND, handler: crate::FileDropHandler) -> Self {
+    Self {
+      vtbl: &VTBL,
+      ref_count: AtomicUsize::new(1),
+      hwnd,
+      handler: Rc::new(handler),
+      hovered_paths: std::cell::RefCell::new(Vec::new()),
+    }
+  }
+
+  fn cursor_position(&self, pt: &POINTL) -> (i32, i32) {
+    let mut point = winapi::shared::windef::POINT { x: pt.x, y: pt.y };
+    // Safety: `point` is a valid, in-bounds out-param and `self.hwnd` is alive for as long as
+    // this `IDropTarget` is registered against it.
+    let scale_factor = unsafe {
+      ScreenToClient(self.hwnd, &mut point);
+      GetDpiForWindow(self.hwnd) as f64 / 96.0
+    };
+    // `ScreenToClient` reports physical pixels; WebView2 and the rest of wry's window/cursor
+    // APIs work in logical (DPI-independent) coordinates, so scale down to match on displays
+    // that aren't at 100% scale.
+    (
+      (point.x as f64 / scale_factor).round() as i32,
+      (point.y as f64 / scale_factor).round() as i32,
+    )
+  }
+
+  fn paths_from(data_object: *mut IDataObject) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if data_object.is_null() {
+      return paths;
+    }
+
+    unsafe {
+      let format = winapi::um::objidl::FORMATETC {
+        cfFormat: winapi::um::winuser::CF_HDROP as u16,
+        ptd: ptr::null_mut(),
+        dwAspect: winapi::um::objidl::DVASPECT_CONTENT,
+        lindex: -1,
+        tymed: winapi::um::objidl::TYMED_HGLOBAL,
+      };
+      let mut medium = std::mem::zeroed();
+      if (*data_object).GetData(&format, &mut medium) != S_OK {
+        return paths;
+      }
+
+      let hdrop = medium.u.hGlobal() as winapi::um::shellapi::HDROP;
+      let count = DragQueryFileW(hdrop, 0xFFFFFFFF, ptr::null_mut(), 0);
+      for i in 0..count {
+        let len = DragQueryFileW(hdrop, i, ptr::null_mut(), 0);
+        let mut buffer = vec![0u16; len as usize + 1];
+        DragQueryFileW(hdrop, i, buffer.as_mut_ptr(), buffer.len() as u32);
+        buffer.pop();
+        paths.push(PathBuf::from(OsString::from_wide(&buffer)));
+      }
+
+      ReleaseStgMedium(&mut medium);
+    }
+
+    paths
+  }
+
+  fn emit(&self, event: FileDropEvent) -> DROPEFFECT {
+    if (self.handler)(event) {
+      DROPEFFECT_COPY
+    } else {
+      DROPEFFECT_NONE
+    }
+  }
+}
+
+unsafe extern "system" fn query_interface(
+  this: *mut IUnknown,
+  riid: REFIID,
+  object: *mut *mut c_void,
+) -> HRESULT {
+  if *riid == IID_IUnknown || *riid == IID_IDropTarget {
+    add_ref(this);
+    *object = this as *mut c_void;
+    S_OK
+  } else {
+    *object = ptr::null_mut();
+    E_NOINTERFACE
+  }
+}
+
+unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+  let target = &*(this as *const FileDropTarget);
+  (target.ref_count.fetch_add(1, Ordering::SeqCst) + 1) as ULONG
+}
+
+unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+  let target = &*(this as *const FileDropTarget);
+  let count = target.ref_count.fetch_sub(1, Ordering::SeqCst) - 1;
+  if count == 0 {
+    drop(Box::from_raw(this as *mut FileDropTarget));
+  }
+  count as ULONG
+}
+
+unsafe extern "system" fn drag_enter(
+  this: *mut IDropTarget,
+  data_object: *mut IDataObject,
+  _key_state: DWORD,
+  pt: POINTL,
+  effect: *mut DROPEFFECT,
+) -> HRESULT {
+  let target = &*(this as *const FileDropTarget);
+  let paths = FileDropTarget::paths_from(data_object);
+  *target.hovered_paths.borrow_mut() = paths.clone();
+  let position = target.cursor_position(&pt);
+  *effect = target.emit(FileDropEvent::Hovered { paths, position });
+  S_OK
+}
+
+unsafe extern "system" fn drag_over(
+  this: *mut IDropTarget,
+  _key_state: DWORD,
+  pt: POINTL,
+  effect: *mut DROPEFFECT,
+) -> HRESULT {
+  let target = &*(this as *const FileDropTarget);
+  let paths = target.hovered_paths.borrow().clone();
+  let position = target.cursor_position(&pt);
+  *effect = target.emit(FileDropEvent::Hovered { paths, position });
+  S_OK
+}
+
+unsafe extern "system" fn drag_leave(this: *mut IDropTarget) -> HRESULT {
+  let target = &*(this as *const FileDropTarget);
+  target.hovered_paths.borrow_mut().clear();
+  let _ = (target.handler)(FileDropEvent::Cancelled);
+  S_OK
+}
+
+unsafe extern "system" fn drop_(
+  this: *mut IDropTarget,
+  data_object: *mut IDataObject,
+  _key_state: DWORD,
+  pt: POINTL,
+  effect: *mut DROPEFFECT,
+) -> HRESULT {
+  let target = &*(this as *const FileDropTarget);
+  let paths = FileDropTarget::paths_from(data_object);
+  target.hovered_paths.borrow_mut().clear();
+  let position = target.cursor_position(&pt);
+  *effect = target.emit(FileDropEvent::Dropped { paths, position });
+  S_OK
+}
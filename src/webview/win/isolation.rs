@@ -0,0 +1,111 @@
+// WebView2 has no built-in equivalent of an "isolation pattern" IPC boundary, so this reimplements
+// the idea directly: when enabled, the bootstrap script installed by
+// `add_script_to_execute_on_document_created` no longer calls `window.chrome.webview.postMessage`
+// itself. Instead it forwards every `window.external.invoke` payload into a sandboxed
+// `<iframe>` that tags it with a per-webview key before relaying it on to the native side.
+//
+// Critically, neither the key nor the call into `chrome.webview.postMessage` ever exists in the
+// main-world page context: both live only inside the iframe's `srcdoc`, a separate JS realm the
+// sandboxed (`allow-scripts`, no `allow-same-origin`) iframe keeps opaque to its parent. The page
+// can still call `window.external.invoke` -- that's the one bridge every legitimate caller needs
+// -- and it can still call `chrome.webview.postMessage` itself, but it has no way to read the key,
+// so it can't forge or bypass the envelope `validate` checks on the way back in.
+
+use rand::RngCore;
+
+/// A fresh, random key identifying one webview's isolation boundary, embedded only inside the
+/// sandboxed iframe (see [`bootstrap_script`]) and never exposed to the page's main-world JS.
+pub fn generate_key() -> String {
+  let mut bytes = [0u8; 32];
+  rand::thread_rng().fill_bytes(&mut bytes);
+  bytes.iter().fold(String::with_capacity(64), |mut hex, byte| {
+    use std::fmt::Write;
+    let _ = write!(hex, "{:02x}", byte);
+    hex
+  })
+}
+
+/// The script installed in place of the plain `window.external={invoke:...}` bootstrap when
+/// isolation is enabled. The main world only ever gets `window.external.invoke`, which hands its
+/// argument to the iframe via `postMessage`; `key` and the subsequent
+/// `chrome.webview.postMessage` call live entirely inside the iframe's `srcdoc`.
+pub fn bootstrap_script(key: &str) -> String {
+  let key_literal = serde_json::to_string(key).unwrap_or_else(|_| "\"\"".into());
+  format!(
+    r#"(function() {{
+  var frame = document.createElement('iframe');
+  frame.style.display = 'none';
+  frame.sandbox = 'allow-scripts';
+  frame.srcdoc =
+    '<script>' +
+    'var key = {key_literal};' +
+    'window.addEventListener("message", function(e) {{' +
+    '  window.chrome.webview.postMessage(JSON.stringify({{ key: key, payload: e.data }}));' +
+    '}});' +
+    '</script>';
+  document.documentElement.appendChild(frame);
+  window.external = {{ invoke: function(s) {{ frame.contentWindow.postMessage(s, '*'); }} }};
+}})();"#,
+    key_literal = key_literal
+  )
+}
+
+/// Strips and checks the `{key, payload}` envelope the isolation bootstrap wraps every message
+/// in, returning the inner payload if `key` matches. Returns `None` (dropping the message) on any
+/// parse failure or key mismatch -- a forged or stale message, not a bug.
+pub fn validate(message: &str, key: &str) -> Option<String> {
+  let value: serde_json::Value = serde_json::from_str(message).ok()?;
+  if value.get("key")?.as_str()? != key {
+    return None;
+  }
+  value.get("payload")?.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn generate_key_is_random_and_full_length() {
+    let a = generate_key();
+    let b = generate_key();
+    assert_ne!(a, b);
+    assert_eq!(a.len(), 64);
+  }
+
+  #[test]
+  fn validate_round_trips_matching_key() {
+    let key = generate_key();
+    let message = serde_json::json!({ "key": key, "payload": "{\"id\":1}" }).to_string();
+    assert_eq!(validate(&message, &key).as_deref(), Some("{\"id\":1}"));
+  }
+
+  #[test]
+  fn validate_rejects_wrong_key() {
+    let message = serde_json::json!({ "key": "a", "payload": "x" }).to_string();
+    assert_eq!(validate(&message, "b"), None);
+  }
+
+  #[test]
+  fn validate_rejects_malformed_json() {
+    assert_eq!(validate("not json", "a"), None);
+  }
+
+  #[test]
+  fn bootstrap_script_keeps_key_and_native_call_inside_the_iframe() {
+    let script = bootstrap_script("deadbeef");
+
+    // The main-world part of the script (everything outside the `srcdoc` string) must not be
+    // able to read the key or call into the native bridge directly.
+    let srcdoc_start = script.find("frame.srcdoc =").unwrap();
+    let srcdoc_end = script.find("document.documentElement").unwrap();
+    let main_world = format!("{}{}", &script[..srcdoc_start], &script[srcdoc_end..]);
+    assert!(!main_world.contains("deadbeef"));
+    assert!(!main_world.contains("chrome.webview.postMessage"));
+
+    // Both live inside the iframe's srcdoc.
+    let srcdoc = &script[srcdoc_start..srcdoc_end];
+    assert!(srcdoc.contains("deadbeef"));
+    assert!(srcdoc.contains("chrome.webview.postMessage"));
+  }
+}
@@ -1,20 +1,53 @@
 mod file_drop;
+mod isolation;
+mod stream;
+pub mod web_context;
 
 use crate::{
-  webview::{mimetype::MimeType, WV},
-  FileDropHandler, Result, RpcHandler,
+  webview::{
+    mimetype::MimeType,
+    web_context::WebContext,
+    web_resource::{
+      self, ByteRange, CspNonce, Request as ProtocolRequest, Response as ProtocolResponse,
+    },
+    WV,
+  },
+  FileDropHandler, NavigationHandler, PageLoadEvent, PageLoadHandler, Result, RpcHandler,
 };
 
 use file_drop::FileDropController;
+use web_context::{EnvironmentHandle, EnvironmentState};
 
-use std::{os::raw::c_void, rc::Rc};
+use std::{
+  cell::RefCell,
+  collections::HashMap,
+  fmt::Write as _,
+  io::{Read, Seek, SeekFrom},
+  os::raw::c_void,
+  rc::Rc,
+};
 
 use once_cell::unsync::OnceCell;
+use rand::RngCore;
+use raw_window_handle::RawWindowHandle;
 use url::Url;
-use webview2::{Controller, PermissionKind, PermissionState};
+use webview2::{Controller, Environment, PermissionKind, PermissionState};
 use winapi::{shared::windef::HWND, um::winuser::GetClientRect};
 use winit::{platform::windows::WindowExtWindows, window::Window};
 
+/// A fresh, random per-request nonce, substituted into a builder-level CSP's `%SCRIPT_NONCE%` /
+/// `%STYLE_NONCE%` placeholders. Must be unguessable -- a predictable nonce defeats the whole
+/// point of a nonce-based CSP, since an attacker who can inject a `<script>` tag just needs to
+/// guess it.
+fn generate_csp_nonce() -> String {
+  let mut bytes = [0u8; 16];
+  rand::thread_rng().fill_bytes(&mut bytes);
+  bytes.iter().fold(String::with_capacity(32), |mut hex, byte| {
+    let _ = write!(hex, "{:02x}", byte);
+    hex
+  })
+}
+
 pub struct InnerWebView {
   controller: Rc<OnceCell<Controller>>,
 
@@ -22,12 +55,18 @@ pub struct InnerWebView {
   // the webview gets dropped, otherwise we'll have a memory leak
   #[allow(dead_code)]
   file_drop_controller: Rc<OnceCell<FileDropController>>,
+
+  // Keeps this webview's claim on its (possibly shared) WebView2 environment alive; dropping the
+  // last claim on it releases the environment, and the backing directory too if it came from an
+  // ephemeral `WebContext`.
+  #[allow(dead_code)]
+  environment_handle: Option<EnvironmentHandle>,
 }
 
 impl WV for InnerWebView {
   type Window = Window;
 
-  fn new<F: 'static + Fn(&str) -> Result<Vec<u8>>>(
+  fn new<F: 'static + Fn(&ProtocolRequest) -> Result<ProtocolResponse>>(
     window: &Window,
     scripts: Vec<String>,
     url: Option<Url>,
@@ -37,157 +76,516 @@ impl WV for InnerWebView {
     custom_protocol: Option<(String, F)>,
     rpc_handler: Option<RpcHandler>,
     file_drop_handler: Option<FileDropHandler>,
+    web_context: Option<&WebContext>,
+    navigation_handler: Option<NavigationHandler>,
+    page_load_handler: Option<PageLoadHandler>,
+    csp: Option<String>,
+    isolation: bool,
   ) -> Result<Self> {
     let hwnd = window.hwnd() as HWND;
+    new_with_hwnd(
+      hwnd,
+      scripts,
+      url,
+      transparent,
+      custom_protocol,
+      rpc_handler,
+      file_drop_handler,
+      web_context,
+      navigation_handler,
+      page_load_handler,
+      csp,
+      isolation,
+    )
+  }
 
-    let controller: Rc<OnceCell<Controller>> = Rc::new(OnceCell::new());
-    let controller_clone = controller.clone();
-
-    let file_drop_controller: Rc<OnceCell<FileDropController>> = Rc::new(OnceCell::new());
-    let file_drop_controller_clone = file_drop_controller.clone();
-
-    // Webview controller
-    webview2::EnvironmentBuilder::new().build(move |env| {
-      let env = env?;
-      let env_ = env.clone();
-      env.create_controller(hwnd, move |controller| {
-        let controller = controller?;
-        let w = controller.get_webview()?;
-
-        // Enable sensible defaults
-        let settings = w.get_settings()?;
-        settings.put_is_status_bar_enabled(false)?;
-        settings.put_are_default_context_menus_enabled(true)?;
-        settings.put_is_zoom_control_enabled(false)?;
-        settings.put_are_dev_tools_enabled(false)?;
-        debug_assert_eq!(settings.put_are_dev_tools_enabled(true)?, ());
-
-        // Safety: System calls are unsafe
-        unsafe {
-          let mut rect = std::mem::zeroed();
-          GetClientRect(hwnd, &mut rect);
-          controller.put_bounds(rect)?;
-        }
+  fn eval(&self, js: &str) -> Result<()> {
+    if let Some(c) = self.controller.get() {
+      let webview = c.get_webview()?;
+      webview.execute_script(js, |_| (Ok(())))?;
+    }
+    Ok(())
+  }
+}
 
-        // Initialize scripts
-        w.add_script_to_execute_on_document_created(
-          "window.external={invoke:s=>window.chrome.webview.postMessage(s)}",
-          |_| (Ok(())),
-        )?;
-        for js in scripts {
-          w.add_script_to_execute_on_document_created(&js, |_| (Ok(())))?;
-        }
+#[allow(clippy::too_many_arguments)]
+fn new_with_hwnd<F: 'static + Fn(&ProtocolRequest) -> Result<ProtocolResponse>>(
+  hwnd: HWND,
+  scripts: Vec<String>,
+  url: Option<Url>,
+  transparent: bool,
+  custom_protocol: Option<(String, F)>,
+  rpc_handler: Option<RpcHandler>,
+  file_drop_handler: Option<FileDropHandler>,
+  web_context: Option<&WebContext>,
+  navigation_handler: Option<NavigationHandler>,
+  page_load_handler: Option<PageLoadHandler>,
+  csp: Option<String>,
+  isolation: bool,
+) -> Result<InnerWebView> {
+  let controller: Rc<OnceCell<Controller>> = Rc::new(OnceCell::new());
+  let controller_clone = controller.clone();
 
-        // Message handler
-        w.add_web_message_received(move |webview, args| {
-          let js = args.try_get_web_message_as_string()?;
-          if let Some(rpc_handler) = rpc_handler.as_ref() {
-            match super::rpc_proxy(js, rpc_handler) {
-              Ok(result) => {
-                if let Some(ref script) = result {
-                  webview.execute_script(script, |_| (Ok(())))?;
-                }
-              }
-              Err(e) => {
-                eprintln!("{}", e);
-              }
-            }
-          }
+  let file_drop_controller: Rc<OnceCell<FileDropController>> = Rc::new(OnceCell::new());
+  let file_drop_controller_clone = file_drop_controller.clone();
+
+  let environment_handle = web_context.map(WebContext::environment_handle);
+  let environment = environment_handle.as_ref().map(|handle| handle.environment());
+  let data_directory = web_context.map(|context| context.data_directory().to_path_buf());
+
+  // Webview controller: reuse the environment a shared `WebContext` has already built, or
+  // build a fresh one (scoped to its data directory, if any) otherwise.
+  with_environment(environment, data_directory, move |env| {
+    let env_ = env.clone();
+    env.create_controller(hwnd, move |controller| {
+      let controller = controller?;
+      let w = controller.get_webview()?;
+
+      // Enable sensible defaults
+      let settings = w.get_settings()?;
+      settings.put_is_status_bar_enabled(false)?;
+      settings.put_are_default_context_menus_enabled(true)?;
+      settings.put_is_zoom_control_enabled(false)?;
+      settings.put_are_dev_tools_enabled(false)?;
+      debug_assert_eq!(settings.put_are_dev_tools_enabled(true)?, ());
+
+      // Safety: System calls are unsafe
+      unsafe {
+        let mut rect = std::mem::zeroed();
+        GetClientRect(hwnd, &mut rect);
+        controller.put_bounds(rect)?;
+      }
+
+      // Initialize scripts. When isolation is enabled, the plain `window.external` bootstrap is
+      // swapped for one that routes every invocation through a sandboxed relay iframe first --
+      // see `isolation` for why that's worth doing.
+      let isolation_key = isolation.then(self::isolation::generate_key);
+      let bootstrap_script = match &isolation_key {
+        Some(key) => self::isolation::bootstrap_script(key),
+        None => "window.external={invoke:s=>window.chrome.webview.postMessage(s)}".to_string(),
+      };
+      w.add_script_to_execute_on_document_created(&bootstrap_script, |_| (Ok(())))?;
+      for js in scripts {
+        w.add_script_to_execute_on_document_created(&js, |_| (Ok(())))?;
+      }
+
+      // Navigation lifecycle: lets the host veto a navigation before it starts, and learn when
+      // a page begins and finishes loading -- things `add_script_to_execute_on_document_created`
+      // alone can't express (e.g. a spinner, or a link allow-list).
+      if let Some(navigation_handler) = navigation_handler {
+        w.add_navigation_starting(move |_, args| {
+          let uri = args.get_uri()?;
+          args.put_cancel(!navigation_handler(uri))?;
           Ok(())
         })?;
+      }
 
-        let mut custom_protocol_name = None;
-        if let Some((name, function)) = custom_protocol {
-          // WebView2 doesn't support non-standard protocols yet, so we have to use this workaround
-          // See https://github.com/MicrosoftEdge/WebView2Feedback/issues/73
-          custom_protocol_name = Some(name.clone());
-          w.add_web_resource_requested_filter(
-            &format!("file://custom-protocol-{}*", name),
-            webview2::WebResourceContext::All,
-          )?;
-          w.add_web_resource_requested(move |_, args| {
-            let uri = args.get_request()?.get_uri()?;
-            // Undo the protocol workaround when giving path to resolver
-            let path = &uri.replace(
-              &format!("file://custom-protocol-{}", name),
-              &format!("{}://", name),
-            );
-
-            match function(path) {
-              Ok(content) => {
-                let mime = MimeType::parse(&content, &uri);
-                let stream = webview2::Stream::from_bytes(&content);
-                let response = env_.create_web_resource_response(
-                  stream,
-                  200,
-                  "OK",
-                  &format!("Content-Type: {}", mime),
-                )?;
-                args.put_response(response)?;
-                Ok(())
-              }
-              Err(_) => Err(webview2::Error::from(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Error loading requested file",
-              ))),
-            }
-          })?;
-        }
+      if let Some(page_load_handler) = page_load_handler {
+        let page_load_handler = Rc::new(page_load_handler);
+        let started_handler = page_load_handler.clone();
+        w.add_content_loading(move |webview, _| {
+          let uri = webview.get_source()?;
+          started_handler(PageLoadEvent::Started, uri);
+          Ok(())
+        })?;
 
-        // Enable clipboard
-        w.add_permission_requested(|_, args| {
-          let kind = args.get_permission_kind()?;
-          if kind == PermissionKind::ClipboardRead {
-            args.put_state(PermissionState::Allow)?;
-          }
+        w.add_navigation_completed(move |webview, _| {
+          let uri = webview.get_source()?;
+          page_load_handler(PageLoadEvent::Finished, uri);
           Ok(())
         })?;
+      }
 
-        // Navigation
-        if let Some(url) = url {
-          let mut url_string = String::from(url.as_str());
-          if let Some(name) = custom_protocol_name {
-            if name == url.scheme() {
-              // WebView2 doesn't support non-standard protocols yet, so we have to use this workaround
-              // See https://github.com/MicrosoftEdge/WebView2Feedback/issues/73
-              url_string = url.as_str().replace(
-                &format!("{}://", name),
-                &format!("file://custom-protocol-{}", name),
-              )
+      // Message handler
+      w.add_web_message_received(move |webview, args| {
+        let js = args.try_get_web_message_as_string()?;
+        let js = match &isolation_key {
+          Some(key) => match self::isolation::validate(&js, key) {
+            Some(payload) => payload,
+            None => {
+              eprintln!("wry: dropped a web message that failed isolation validation");
+              return Ok(());
+            }
+          },
+          None => js,
+        };
+        if let Some(rpc_handler) = rpc_handler.as_ref() {
+          match super::rpc_proxy(js, rpc_handler) {
+            Ok(result) => {
+              if let Some(ref script) = result {
+                webview.execute_script(script, |_| (Ok(())))?;
+              }
+            }
+            Err(e) => {
+              eprintln!("{}", e);
             }
           }
-          w.navigate(&url_string)?;
         }
+        Ok(())
+      })?;
 
-        let _ = controller_clone.set(controller);
+      // `csp` is only ever applied to custom-protocol responses (as a response header, below) --
+      // WebView2 gives us no hook to set a response header, or inject a `<meta>` tag before a
+      // page's own content, for an ordinary `https://`/`file://` navigation that bypasses
+      // `add_web_resource_requested` entirely. So a `csp` without a `custom_protocol` is silently
+      // a no-op; warn loudly about it instead, since that's a much easier mistake to make than it
+      // is to notice.
+      if csp.is_some() && custom_protocol.is_none() {
+        eprintln!(
+          "wry: `csp` has no effect without a `custom_protocol` on Windows -- it's only applied \
+           to custom-protocol responses, so a plain navigation won't be restricted by it"
+        );
+      }
 
-        if let Some(file_drop_handler) = file_drop_handler {
-          let mut file_drop_controller = FileDropController::new();
-          file_drop_controller.listen(hwnd, file_drop_handler);
-          let _ = file_drop_controller_clone.set(file_drop_controller);
-        }
+      let mut custom_protocol_name = None;
+      if let Some((name, function)) = custom_protocol {
+        // WebView2 doesn't support non-standard protocols yet, so we have to use this workaround
+        // See https://github.com/MicrosoftEdge/WebView2Feedback/issues/73
+        custom_protocol_name = Some(name.clone());
+        w.add_web_resource_requested_filter(
+          &format!("file://custom-protocol-{}*", name),
+          webview2::WebResourceContext::All,
+        )?;
+        let csp = csp.clone();
+        w.add_web_resource_requested(move |_, args| {
+          let webview_request = args.get_request()?;
+          let uri = webview_request.get_uri()?;
+          // Undo the protocol workaround when giving path to resolver
+          let path = uri.replace(
+            &format!("file://custom-protocol-{}", name),
+            &format!("{}://", name),
+          );
+          let headers = request_headers(&webview_request)?;
+          let range = headers.get("range").cloned();
+          let csp_nonce = csp.as_ref().map(|_| CspNonce {
+            script: generate_csp_nonce(),
+            style: generate_csp_nonce(),
+          });
+
+          match function(&ProtocolRequest {
+            uri: path,
+            headers,
+            csp_nonce: csp_nonce.clone(),
+          }) {
+            Ok(mut protocol_response) => {
+              // `body` is `Seek`, so we can get its length by seeking to the end and back
+              // instead of buffering it to find out.
+              let len = protocol_response.body.seek(SeekFrom::End(0))?;
+              protocol_response.body.seek(SeekFrom::Start(0))?;
+
+              let mime = match protocol_response.headers.get("Content-Type") {
+                Some(mime) => mime.clone(),
+                None => {
+                  // Only sniff a small prefix, not the whole body.
+                  let mut prefix = vec![0u8; len.min(512) as usize];
+                  protocol_response.body.read_exact(&mut prefix)?;
+                  protocol_response.body.seek(SeekFrom::Start(0))?;
+                  MimeType::parse(&prefix, &uri)
+                }
+              };
+
+              let parsed_range = range.as_deref().map(|header| ByteRange::parse(header, len));
 
+              let (status, reason, body_stream, content_length, mut extra_headers) =
+                match parsed_range {
+                  Some(Ok(range)) => {
+                    // A range is typically a small slice of the body, so reading just that slice
+                    // into memory (rather than standing up a whole `IStream` for it) is simplest.
+                    protocol_response.body.seek(SeekFrom::Start(range.start))?;
+                    let mut payload = vec![0u8; range.byte_count() as usize];
+                    protocol_response.body.read_exact(&mut payload)?;
+                    (
+                      206,
+                      "Partial Content",
+                      webview2::Stream::from_bytes(&payload),
+                      payload.len() as u64,
+                      format!(
+                        "Content-Range: bytes {}-{}/{}\r\nAccept-Ranges: bytes\r\n",
+                        range.start, range.end, len
+                      ),
+                    )
+                  }
+                  Some(Err(web_resource::RangeError::Unsatisfiable)) => (
+                    416,
+                    "Range Not Satisfiable",
+                    webview2::Stream::from_bytes(&[]),
+                    0,
+                    format!("Content-Range: bytes */{}\r\nAccept-Ranges: bytes\r\n", len),
+                  ),
+                  // No `Range` header, or one we couldn't parse -- per RFC 7233, an invalid Range
+                  // is ignored rather than rejected, so both serve the full body. This is also the
+                  // common case (the initial, non-seeking load of any resource), so the body is
+                  // handed to WebView2 as a lazily-read `IStream` instead of being buffered into a
+                  // `Vec` up front -- see `stream` for why `Stream::from_bytes` can't do that.
+                  Some(Err(web_resource::RangeError::Malformed)) | None => {
+                    let body_stream = stream::from_body(protocol_response.body, len);
+                    (
+                      200,
+                      "OK",
+                      // Safety: `body_stream` is a freshly-created `IStream` holding one
+                      // reference, which `Stream::from_raw` takes ownership of.
+                      unsafe { webview2::Stream::from_raw(body_stream) },
+                      len,
+                      String::from("Accept-Ranges: bytes\r\n"),
+                    )
+                  }
+                };
+
+              // Per-load CSP, nonced for this request, unless the resolver already set its own.
+              if let Some(own) = protocol_response.headers.get("Content-Security-Policy") {
+                extra_headers.push_str(&format!("Content-Security-Policy: {}\r\n", own));
+              } else if let (Some(csp), Some(nonce)) = (&csp, &csp_nonce) {
+                let policy = csp
+                  .replace("%SCRIPT_NONCE%", &nonce.script)
+                  .replace("%STYLE_NONCE%", &nonce.style);
+                extra_headers.push_str(&format!("Content-Security-Policy: {}\r\n", policy));
+              }
+
+              let header_string = format!(
+                "Content-Type: {}\r\n{}Content-Length: {}",
+                mime, extra_headers, content_length
+              );
+              let response = env_.create_web_resource_response(
+                body_stream,
+                status,
+                reason,
+                &header_string,
+              )?;
+              args.put_response(response)?;
+              Ok(())
+            }
+            Err(_) => Err(webview2::Error::from(std::io::Error::new(
+              std::io::ErrorKind::Other,
+              "Error loading requested file",
+            ))),
+          }
+        })?;
+      }
+
+      // Enable clipboard
+      w.add_permission_requested(|_, args| {
+        let kind = args.get_permission_kind()?;
+        if kind == PermissionKind::ClipboardRead {
+          args.put_state(PermissionState::Allow)?;
+        }
         Ok(())
-      })
-    })?;
+      })?;
+
+      // Navigation
+      if let Some(url) = url {
+        let mut url_string = String::from(url.as_str());
+        if let Some(name) = custom_protocol_name {
+          if name == url.scheme() {
+            // WebView2 doesn't support non-standard protocols yet, so we have to use this workaround
+            // See https://github.com/MicrosoftEdge/WebView2Feedback/issues/73
+            url_string = url.as_str().replace(
+              &format!("{}://", name),
+              &format!("file://custom-protocol-{}", name),
+            )
+          }
+        }
+        w.navigate(&url_string)?;
+      }
 
-    Ok(Self {
-      controller,
+      let _ = controller_clone.set(controller);
 
-      file_drop_controller,
+      if let Some(file_drop_handler) = file_drop_handler {
+        let mut file_drop_controller = FileDropController::new();
+        file_drop_controller.listen(hwnd, file_drop_handler);
+        let _ = file_drop_controller_clone.set(file_drop_controller);
+      }
+
+      Ok(())
     })
+  })?;
+
+  Ok(InnerWebView {
+    controller,
+
+    file_drop_controller,
+    environment_handle,
+  })
+}
+
+/// Collects a WebView2 resource request's headers into a lower-cased-name map so custom-protocol
+/// resolvers (and our own `Range` handling) can read them without caring about casing.
+fn request_headers(
+  request: &webview2::WebResourceRequest,
+) -> webview2::Result<HashMap<String, String>> {
+  let mut headers = HashMap::new();
+  let mut iter = request.get_headers()?.get_iterator()?;
+  while iter.has_current_header()? {
+    let (name, value) = iter.get_current_header()?;
+    headers.insert(name.to_lowercase(), value);
+    iter.move_next()?;
   }
+  Ok(headers)
+}
 
-  fn eval(&self, js: &str) -> Result<()> {
-    if let Some(c) = self.controller.get() {
-      let webview = c.get_webview()?;
-      webview.execute_script(js, |_| (Ok(())))?;
+/// Hands a built WebView2 `Environment` to `f`, reusing one already cached in `environment` (by
+/// a shared `WebContext`) if present, or building one scoped to `data_directory` and caching it
+/// there otherwise.
+///
+/// `EnvironmentBuilder::build` completes asynchronously, so two `InnerWebView`s sharing a
+/// `WebContext` can both reach this function before either build finishes. To avoid each kicking
+/// off its own `Environment` (and silently not sharing state as the `WebContext` promises), the
+/// first caller to see `Empty` is the only one that ever calls `builder.build`; everyone else
+/// who arrives while that build is in flight is queued in `Building` and invoked once it lands.
+fn with_environment<F>(
+  environment: Option<Rc<RefCell<EnvironmentState>>>,
+  data_directory: Option<std::path::PathBuf>,
+  f: F,
+) -> webview2::Result<()>
+where
+  F: FnOnce(Environment) -> webview2::Result<()> + 'static,
+{
+  let mut f = Some(f);
+
+  if let Some(cell) = &environment {
+    let mut state = cell.borrow_mut();
+    match &mut *state {
+      EnvironmentState::Built(env) => {
+        let env = env.clone();
+        drop(state);
+        return f.take().unwrap()(env);
+      }
+      EnvironmentState::Building(pending) => {
+        let f = f.take().unwrap();
+        pending.push(Box::new(move |env| {
+          if let Ok(env) = env {
+            let _ = f(env);
+          }
+        }));
+        return Ok(());
+      }
+      EnvironmentState::Empty => {
+        *state = EnvironmentState::Building(Vec::new());
+      }
     }
-    Ok(())
   }
+
+  let mut builder = webview2::EnvironmentBuilder::new();
+  if let Some(data_directory) = &data_directory {
+    builder = builder.with_user_data_folder(data_directory);
+  }
+  let f = f.take().unwrap();
+  builder.build(move |env| match env {
+    Ok(env) => {
+      if let Some(cell) = &environment {
+        let previous = cell.replace(EnvironmentState::Built(env.clone()));
+        if let EnvironmentState::Building(pending) = previous {
+          for waiter in pending {
+            waiter(Ok(env.clone()));
+          }
+        }
+      }
+      f(env)
+    }
+    Err(e) => {
+      // The build never landed, so there's nothing to cache -- reset to `Empty` (instead of
+      // leaving `Building` stuck forever) so the next caller retries instead of queuing behind
+      // a build that's already over, and tell everyone already queued behind this one that it
+      // failed instead of leaving them hanging.
+      if let Some(cell) = &environment {
+        let previous = cell.replace(EnvironmentState::Empty);
+        if let EnvironmentState::Building(pending) = previous {
+          for waiter in pending {
+            waiter(Err(webview2::Error::from(std::io::Error::new(
+              std::io::ErrorKind::Other,
+              "webview2 environment failed to build",
+            ))));
+          }
+        }
+      }
+      Err(e)
+    }
+  })
 }
 
 impl InnerWebView {
+  /// Builds a webview bound to `window_handle` directly, instead of requiring wry to own a
+  /// [`winit::window::Window`]. This is what lets wry be embedded as a component inside a window
+  /// owned by another UI toolkit (a plain Win32 host, FLTK, ...).
+  #[allow(clippy::too_many_arguments)]
+  pub fn new_as_child<F: 'static + Fn(&ProtocolRequest) -> Result<ProtocolResponse>>(
+    window_handle: RawWindowHandle,
+    scripts: Vec<String>,
+    url: Option<Url>,
+    transparent: bool,
+    custom_protocol: Option<(String, F)>,
+    rpc_handler: Option<RpcHandler>,
+    file_drop_handler: Option<FileDropHandler>,
+    web_context: Option<&WebContext>,
+    navigation_handler: Option<NavigationHandler>,
+    page_load_handler: Option<PageLoadHandler>,
+    csp: Option<String>,
+    isolation: bool,
+  ) -> Result<Self> {
+    let hwnd = match window_handle {
+      RawWindowHandle::Windows(handle) => handle.hwnd as HWND,
+      _ => {
+        return Err(
+          webview2::Error::from(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the Windows backend only supports a Windows `RawWindowHandle`",
+          ))
+          .into(),
+        )
+      }
+    };
+
+    new_with_hwnd(
+      hwnd,
+      scripts,
+      url,
+      transparent,
+      custom_protocol,
+      rpc_handler,
+      file_drop_handler,
+      web_context,
+      navigation_handler,
+      page_load_handler,
+      csp,
+      isolation,
+    )
+  }
+
+  /// Like [`WV::eval`], but delivers the JSON-encoded result of evaluating `js` to `callback`
+  /// instead of discarding it. Useful for reading back DOM state or a computed value; if you
+  /// don't need the result, `eval` remains the cheaper fire-and-forget path.
+  pub fn evaluate_script_with_callback(
+    &self,
+    js: &str,
+    callback: impl FnOnce(Result<String>) + 'static,
+  ) -> Result<()> {
+    let controller = match self.controller.get() {
+      Some(controller) => controller,
+      // A caller driving `callback` with a oneshot channel or future would otherwise hang
+      // forever waiting on a callback that's never coming.
+      None => {
+        callback(Err(
+          webview2::Error::from(std::io::Error::new(
+            std::io::ErrorKind::NotConnected,
+            "the webview isn't initialized yet",
+          ))
+          .into(),
+        ));
+        return Ok(());
+      }
+    };
+    let webview = controller.get_webview()?;
+    // `execute_script`'s completion handler must be re-callable (`FnMut`), so stash our
+    // once-only `callback` behind an `Option` and take it when the result comes back.
+    let mut callback = Some(callback);
+    webview.execute_script(js, move |result| {
+      if let Some(callback) = callback.take() {
+        callback(result.map_err(Into::into));
+      }
+      Ok(())
+    })?;
+    Ok(())
+  }
+
   pub fn resize(&self, hwnd: *mut c_void) -> Result<()> {
     let hwnd = hwnd as HWND;
 
@@ -0,0 +1,206 @@
+// `webview2::Stream::from_bytes` only ever wraps an already-materialized `Vec<u8>`, which is
+// exactly what defeats streaming for a full, non-`Range` body: the whole resource still has to be
+// read into memory before WebView2 gets to see a single byte of it. This implements a minimal,
+// read-only COM `IStream` directly over a response's `Read + Seek` body instead (the same
+// hand-rolled-COM approach `file_drop` already uses for `IDropTarget`), so WebView2 pulls bytes
+// from the body lazily, as it actually needs them, instead of us buffering it up front.
+//
+// Only `Read`/`Seek`/`Stat` are implemented for real; the rest of `IStream` (`Write`, `SetSize`,
+// `CopyTo`, `Commit`, `Revert`, `Lock`/`UnlockRegion`, `Clone`) is unused by a read-only HTTP-style
+// response body, so those are plain `E_NOTIMPL` stubs.
+
+use std::{
+  cell::RefCell,
+  io::{Read, Seek, SeekFrom},
+  ptr,
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+use winapi::{
+  ctypes::c_void,
+  shared::{
+    guiddef::REFIID,
+    minwindef::{DWORD, ULONG},
+    winerror::{E_FAIL, E_NOINTERFACE, E_NOTIMPL, HRESULT, S_OK},
+  },
+  um::{
+    objidl::{IStream, IStreamVtbl, ISequentialStreamVtbl, IID_IStream, STATSTG, STGTY_STREAM},
+    unknwnbase::{IUnknown, IUnknownVtbl, IID_IUnknown},
+    winnt::{LARGE_INTEGER, ULARGE_INTEGER},
+  },
+};
+
+#[repr(C)]
+struct BodyStream {
+  vtbl: *const IStreamVtbl,
+  ref_count: AtomicUsize,
+  body: RefCell<Box<dyn Read + Seek>>,
+  len: u64,
+}
+
+static VTBL: IStreamVtbl = IStreamVtbl {
+  parent: ISequentialStreamVtbl {
+    parent: IUnknownVtbl {
+      QueryInterface: query_interface,
+      AddRef: add_ref,
+      Release: release,
+    },
+    Read: read,
+    Write: write,
+  },
+  Seek: seek,
+  SetSize: set_size,
+  CopyTo: copy_to,
+  Commit: commit,
+  Revert: revert,
+  LockRegion: lock_region,
+  UnlockRegion: unlock_region,
+  Stat: stat,
+  Clone: clone_stream,
+};
+
+/// Wraps `body` (of `len` bytes) in a COM `IStream` WebView2 can read lazily, instead of
+/// requiring the whole thing to be buffered into a `Vec<u8>` first. The returned pointer owns one
+/// reference, released the same way any other COM object's is -- whoever takes it (here,
+/// `webview2::Stream`) is responsible for calling `Release` when it's done with it.
+pub fn from_body(body: Box<dyn Read + Seek>, len: u64) -> *mut IStream {
+  let stream = Box::new(BodyStream {
+    vtbl: &VTBL,
+    ref_count: AtomicUsize::new(1),
+    body: RefCell::new(body),
+    len,
+  });
+  Box::into_raw(stream) as *mut IStream
+}
+
+unsafe extern "system" fn query_interface(
+  this: *mut IUnknown,
+  riid: REFIID,
+  object: *mut *mut c_void,
+) -> HRESULT {
+  if *riid == IID_IUnknown || *riid == IID_IStream {
+    add_ref(this);
+    *object = this as *mut c_void;
+    S_OK
+  } else {
+    *object = ptr::null_mut();
+    E_NOINTERFACE
+  }
+}
+
+unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+  let stream = &*(this as *const BodyStream);
+  (stream.ref_count.fetch_add(1, Ordering::SeqCst) + 1) as ULONG
+}
+
+unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+  let stream = &*(this as *const BodyStream);
+  let count = stream.ref_count.fetch_sub(1, Ordering::SeqCst) - 1;
+  if count == 0 {
+    drop(Box::from_raw(this as *mut BodyStream));
+  }
+  count as ULONG
+}
+
+unsafe extern "system" fn read(
+  this: *mut IStream,
+  buffer: *mut c_void,
+  count: ULONG,
+  read_count: *mut ULONG,
+) -> HRESULT {
+  let stream = &*(this as *const BodyStream);
+  let out = std::slice::from_raw_parts_mut(buffer as *mut u8, count as usize);
+  // A short read (e.g. hitting EOF) isn't an error for `IStream::Read` -- just report however
+  // many bytes actually came back, same as the `ULONG* pcbRead` contract calls for.
+  let read = stream.body.borrow_mut().read(out).unwrap_or(0);
+  if !read_count.is_null() {
+    *read_count = read as ULONG;
+  }
+  S_OK
+}
+
+unsafe extern "system" fn write(
+  _this: *mut IStream,
+  _buffer: *const c_void,
+  _count: ULONG,
+  _written: *mut ULONG,
+) -> HRESULT {
+  E_NOTIMPL
+}
+
+unsafe extern "system" fn seek(
+  this: *mut IStream,
+  move_: LARGE_INTEGER,
+  origin: DWORD,
+  new_position: *mut ULARGE_INTEGER,
+) -> HRESULT {
+  let stream = &*(this as *const BodyStream);
+  let offset = *move_.QuadPart();
+  // STREAM_SEEK_SET / _CUR / _END, per the `IStream::Seek` contract.
+  let from = match origin {
+    1 => SeekFrom::Current(offset),
+    2 => SeekFrom::End(offset),
+    _ => SeekFrom::Start(offset as u64),
+  };
+  match stream.body.borrow_mut().seek(from) {
+    Ok(position) => {
+      if !new_position.is_null() {
+        *(*new_position).QuadPart_mut() = position as i64;
+      }
+      S_OK
+    }
+    Err(_) => E_FAIL,
+  }
+}
+
+unsafe extern "system" fn set_size(_this: *mut IStream, _size: ULARGE_INTEGER) -> HRESULT {
+  E_NOTIMPL
+}
+
+unsafe extern "system" fn copy_to(
+  _this: *mut IStream,
+  _dest: *mut IStream,
+  _count: ULARGE_INTEGER,
+  _read: *mut ULARGE_INTEGER,
+  _written: *mut ULARGE_INTEGER,
+) -> HRESULT {
+  E_NOTIMPL
+}
+
+unsafe extern "system" fn commit(_this: *mut IStream, _flags: DWORD) -> HRESULT {
+  E_NOTIMPL
+}
+
+unsafe extern "system" fn revert(_this: *mut IStream) -> HRESULT {
+  E_NOTIMPL
+}
+
+unsafe extern "system" fn lock_region(
+  _this: *mut IStream,
+  _offset: ULARGE_INTEGER,
+  _count: ULARGE_INTEGER,
+  _lock_type: DWORD,
+) -> HRESULT {
+  E_NOTIMPL
+}
+
+unsafe extern "system" fn unlock_region(
+  _this: *mut IStream,
+  _offset: ULARGE_INTEGER,
+  _count: ULARGE_INTEGER,
+  _lock_type: DWORD,
+) -> HRESULT {
+  E_NOTIMPL
+}
+
+unsafe extern "system" fn stat(this: *mut IStream, out: *mut STATSTG, _flags: DWORD) -> HRESULT {
+  let stream = &*(this as *const BodyStream);
+  ptr::write_bytes(out, 0, 1);
+  (*out).type_ = STGTY_STREAM;
+  *(*out).cbSize.QuadPart_mut() = stream.len as i64;
+  S_OK
+}
+
+unsafe extern "system" fn clone_stream(_this: *mut IStream, _out: *mut *mut IStream) -> HRESULT {
+  E_NOTIMPL
+}
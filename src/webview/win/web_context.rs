@@ -0,0 +1,89 @@
+use std::{
+  cell::RefCell,
+  collections::{hash_map::Entry, HashMap},
+  path::PathBuf,
+  rc::Rc,
+};
+
+use webview2::Environment;
+
+/// The shared WebView2 `Environment` backing one data directory, built lazily and (since
+/// `EnvironmentBuilder::build` completes asynchronously) only ever built once even if several
+/// `InnerWebView`s sharing a `WebContext` ask for it before the first build finishes.
+pub enum EnvironmentState {
+  Empty,
+  /// A build is already in flight; these are the other callers waiting on it, invoked with the
+  /// built environment once it lands instead of each kicking off their own. Takes a `Result`
+  /// rather than a bare `Environment` so a failed build can tell every queued caller it failed,
+  /// instead of leaving them waiting on a build that's already over.
+  Building(Vec<Box<dyn FnOnce(webview2::Result<Environment>)>>),
+  Built(Environment),
+}
+
+struct Shared {
+  environment: Rc<RefCell<EnvironmentState>>,
+  ref_count: usize,
+}
+
+thread_local! {
+  // Keyed by data directory so every `InnerWebView` sharing a `WebContext` also shares the
+  // WebView2 `Environment` (and therefore its cookies/cache) that environment owns.
+  static ENVIRONMENTS: RefCell<HashMap<PathBuf, Shared>> = RefCell::new(HashMap::new());
+}
+
+/// One `InnerWebView`'s claim on a shared WebView2 environment. Built alongside the webview and
+/// dropped alongside it; releases the environment once the last claim on a given key goes away,
+/// deleting the data directory too if the owning [`WebContext`](crate::WebContext) is ephemeral.
+pub struct EnvironmentHandle {
+  key: PathBuf,
+  ephemeral: bool,
+}
+
+impl EnvironmentHandle {
+  pub fn new(data_directory: PathBuf, ephemeral: bool) -> Self {
+    ENVIRONMENTS.with(|environments| {
+      environments
+        .borrow_mut()
+        .entry(data_directory.clone())
+        .or_insert_with(|| Shared {
+          environment: Rc::new(RefCell::new(EnvironmentState::Empty)),
+          ref_count: 0,
+        })
+        .ref_count += 1;
+    });
+
+    Self {
+      key: data_directory,
+      ephemeral,
+    }
+  }
+
+  /// The (possibly not-yet-built, possibly already-building) environment state claimed by this
+  /// handle.
+  pub fn environment(&self) -> Rc<RefCell<EnvironmentState>> {
+    ENVIRONMENTS.with(|environments| {
+      environments
+        .borrow()
+        .get(&self.key)
+        .expect("environment entry should outlive every handle referencing it")
+        .environment
+        .clone()
+    })
+  }
+}
+
+impl Drop for EnvironmentHandle {
+  fn drop(&mut self) {
+    ENVIRONMENTS.with(|environments| {
+      if let Entry::Occupied(mut entry) = environments.borrow_mut().entry(self.key.clone()) {
+        entry.get_mut().ref_count -= 1;
+        if entry.get().ref_count == 0 {
+          entry.remove();
+          if self.ephemeral {
+            let _ = std::fs::remove_dir_all(&self.key);
+          }
+        }
+      }
+    });
+  }
+}